@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use discord_rich_presence::activity::Activity;
+use discord_rich_presence::{DiscordIpc, DiscordIpcClient};
+use steamid_ng::SteamID;
+
+use crate::player::{Player, PlayerState};
+use crate::player_records::Verdict;
+use crate::settings::DiscordRichPresenceSettings;
+
+/// Drives an optional Discord Rich Presence connection off the current TF2 session.
+///
+/// Every method is a no-op when the feature is disabled in settings, so callers can
+/// wire this in unconditionally rather than branching on configuration at every call
+/// site.
+pub struct DiscordPresence {
+    client: Option<DiscordIpcClient>,
+    enabled: bool,
+}
+
+impl DiscordPresence {
+    pub fn new(settings: &DiscordRichPresenceSettings) -> Self {
+        let mut presence = Self {
+            client: None,
+            enabled: settings.enabled,
+        };
+        presence.connect(settings);
+        presence
+    }
+
+    fn connect(&mut self, settings: &DiscordRichPresenceSettings) {
+        if !self.enabled {
+            return;
+        }
+
+        match DiscordIpcClient::new(&settings.client_id) {
+            Ok(mut client) => match client.connect() {
+                Ok(()) => self.client = Some(client),
+                Err(e) => {
+                    tracing::warn!("Failed to connect to Discord IPC, will retry later: {e}");
+                    self.client = None;
+                }
+            },
+            Err(e) => {
+                tracing::warn!("Failed to create Discord IPC client: {e}");
+                self.client = None;
+            }
+        }
+    }
+
+    /// Publishes the current session (map/server, live player count, verdict stats) as
+    /// the user's Discord activity, reconnecting first if Discord dropped the IPC pipe.
+    pub fn update(
+        &mut self,
+        settings: &DiscordRichPresenceSettings,
+        server_name: &str,
+        map_name: &str,
+        players: &HashMap<SteamID, Player>,
+    ) {
+        if !self.enabled {
+            return;
+        }
+
+        if self.client.is_none() {
+            self.connect(settings);
+        }
+
+        let Some(client) = self.client.as_mut() else {
+            return;
+        };
+
+        let live_players = players
+            .values()
+            .filter(|p| p.game_info.state == PlayerState::Active)
+            .count();
+        let flagged = players
+            .values()
+            .filter(|p| p.convicted || matches!(p.local_verdict, Verdict::Cheater | Verdict::Bot))
+            .count();
+
+        let details = format!("{map_name} on {server_name}");
+        let state = if flagged > 0 {
+            format!("{live_players} players - {flagged} bots flagged")
+        } else {
+            format!("{live_players} players")
+        };
+
+        let activity = Activity::new().details(&details).state(&state);
+
+        if let Err(e) = client.set_activity(activity) {
+            tracing::warn!("Failed to update Discord activity, will reconnect next cycle: {e}");
+            self.client = None;
+        }
+    }
+
+    /// Clears the activity and drops the IPC connection, e.g. once `should_prune`
+    /// detects the local player has disconnected from the game.
+    pub fn clear(&mut self) {
+        let Some(mut client) = self.client.take() else {
+            return;
+        };
+
+        if let Err(e) = client.clear_activity() {
+            tracing::warn!("Failed to clear Discord activity: {e}");
+        }
+        let _ = client.close();
+    }
+}
@@ -1,5 +1,6 @@
 pub mod app;
 pub(crate) mod demo;
+pub(crate) mod discord;
 pub(crate) mod gamefinder;
 pub mod io;
 pub(crate) mod launchoptions;
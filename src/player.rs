@@ -5,7 +5,7 @@ use steamid_ng::SteamID;
 
 use crate::{
     io::{g15::G15Player, regexes::StatusLine},
-    player_records::{PlayerRecord, Verdict},
+    player_records::{PlayerRecord, SourcedVerdict, Verdict},
 };
 
 // Player
@@ -27,8 +27,27 @@ pub struct Player {
     #[serde(rename = "localVerdict")]
     pub local_verdict: Verdict,
     pub convicted: bool,
+    /// Whether the consensus of this player's remote-list sources (alone, ignoring
+    /// `local_verdict`) is a cheater/bot verdict. Kept separate from `convicted`,
+    /// which is the user's own manual judgement, so importing a shared list can
+    /// never silently clear or set it.
+    #[serde(rename = "remoteConvicted")]
+    pub remote_convicted: bool,
+    /// `local_verdict` and the remote-source consensus resolved through the
+    /// configured `MergePolicy`. Purely a derived view for display — never fed back
+    /// into `local_verdict` or `convicted`.
+    #[serde(rename = "effectiveVerdict")]
+    pub effective_verdict: Verdict,
     #[serde(rename = "previousNames")]
     pub previous_names: Vec<Arc<str>>,
+    /// Id of the connected-component this player belongs to in the friend-network
+    /// bot-cluster detector, if it's been grouped with other convicted accounts.
+    #[serde(rename = "friendCluster")]
+    pub friend_cluster: Option<usize>,
+    /// Verdicts contributed by imported remote lists, each attributed to its source.
+    /// Kept separate from `local_verdict` so users can see who flagged a player and
+    /// why without it clobbering their own manual judgement.
+    pub sources: Vec<SourcedVerdict>,
 }
 
 impl Player {
@@ -44,7 +63,11 @@ impl Player {
             tags: Vec::new(),
             local_verdict: Verdict::Player,
             convicted: false,
+            remote_convicted: false,
+            effective_verdict: Verdict::Player,
             previous_names: Vec::new(),
+            friend_cluster: None,
+            sources: Vec::new(),
         }
     }
 
@@ -63,20 +86,34 @@ impl Player {
             tags: Vec::new(),
             local_verdict: Verdict::Player,
             convicted: false,
+            remote_convicted: false,
+            effective_verdict: Verdict::Player,
             previous_names: Vec::new(),
+            friend_cluster: None,
+            sources: Vec::new(),
         })
     }
 
-    /// Given a record, update this player with the included data.
-    pub fn update_from_record(&mut self, record: PlayerRecord) {
+    /// Given a record, update this player with the included data. Returns whether the
+    /// local verdict changed, so callers can emit a `VerdictChanged` event.
+    pub fn update_from_record(&mut self, record: PlayerRecord) -> bool {
         if record.steamid != self.steamid {
             tracing::error!("Updating player with wrong record.");
-            return;
+            return false;
         }
 
+        let verdict_changed = self.local_verdict != record.verdict;
+
         self.custom_data = record.custom_data;
         self.local_verdict = record.verdict;
         self.previous_names = record.previous_names;
+
+        verdict_changed
+    }
+
+    /// Sets this player's resolved Steam profile info.
+    pub fn set_steam_info(&mut self, info: SteamInfo) {
+        self.steam_info = Some(info);
     }
 
     /// Create a record from the current player
@@ -150,7 +187,7 @@ pub struct SteamInfo {
     pub vac_bans: i64,
     pub game_bans: i64,
     pub days_since_last_ban: Option<i64>,
-    // pub friends: Vec<Friend>,
+    pub friends: Vec<Friend>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -225,21 +262,30 @@ impl GameInfo {
         }
     }
 
-    pub(crate) fn next_cycle(&mut self) {
+    /// Advances this player's last-seen counter, marking it disconnected once it passes
+    /// the threshold. Returns whether `state` changed, so callers can emit a
+    /// `StateChanged` event.
+    pub(crate) fn next_cycle(&mut self) -> bool {
         const DISCONNECTED_THRESHOLD: u32 = 1;
 
         self.last_seen += 1;
-        if self.last_seen > DISCONNECTED_THRESHOLD {
+        if self.last_seen > DISCONNECTED_THRESHOLD && self.state != PlayerState::Disconnected {
             self.state = PlayerState::Disconnected;
+            return true;
         }
+        false
     }
 
-    pub(crate) fn acknowledge(&mut self) {
+    /// Resets the last-seen counter and un-disconnects the player if needed. Returns
+    /// whether `state` changed, so callers can emit a `StateChanged` event.
+    pub(crate) fn acknowledge(&mut self) -> bool {
         self.last_seen = 0;
 
         if self.state == PlayerState::Disconnected {
             self.state = PlayerState::Spawning;
+            return true;
         }
+        false
     }
 
     pub(crate) fn should_prune(&self) -> bool {
@@ -250,6 +296,6 @@ impl GameInfo {
 
 // Useful
 
-fn serialize_steamid_as_string<S: Serializer>(steamid: &SteamID, s: S) -> Result<S::Ok, S::Error> {
+pub(crate) fn serialize_steamid_as_string<S: Serializer>(steamid: &SteamID, s: S) -> Result<S::Ok, S::Error> {
     format!("{}", u64::from(*steamid)).serialize(s)
 }
@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use steamid_ng::SteamID;
+
+use crate::player::Player;
+
+/// A user's local judgement of a player, persisted alongside their steamid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Player,
+    Bot,
+    Cheater,
+    Suspicious,
+    Trusted,
+}
+
+impl Verdict {
+    /// Relative severity used by [`MergePolicy::MostSevereWins`], most severe first.
+    fn severity(self) -> u8 {
+        match self {
+            Verdict::Cheater => 4,
+            Verdict::Bot => 3,
+            Verdict::Suspicious => 2,
+            Verdict::Player => 1,
+            Verdict::Trusted => 0,
+        }
+    }
+}
+
+/// A persisted record of a player, keyed by steamid.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerRecord {
+    #[serde(rename = "steamID64", serialize_with = "serialize_steamid", deserialize_with = "deserialize_steamid")]
+    pub steamid: SteamID,
+    pub verdict: Verdict,
+    #[serde(rename = "customData")]
+    pub custom_data: serde_json::Value,
+    #[serde(rename = "previousNames")]
+    pub previous_names: Vec<Arc<str>>,
+}
+
+fn serialize_steamid<S: Serializer>(steamid: &SteamID, s: S) -> Result<S::Ok, S::Error> {
+    format!("{}", u64::from(*steamid)).serialize(s)
+}
+
+fn deserialize_steamid<'de, D: Deserializer<'de>>(d: D) -> Result<SteamID, D::Error> {
+    let raw = String::deserialize(d)?;
+    raw.parse::<u64>()
+        .map(SteamID::from)
+        .map_err(serde::de::Error::custom)
+}
+
+// Shared playerlist import/export
+
+/// Schema version for imported/exported player-list batches. Bump whenever
+/// `PlayerListEntry`'s shape changes in a way that isn't backwards compatible, so
+/// older clients can at least detect and skip a batch they don't understand.
+pub const PLAYER_LIST_SCHEMA_VERSION: u32 = 1;
+
+/// One entry in an imported/exported player-list batch. Mirrors `PlayerRecord`, but
+/// every field beyond `steamid`/`verdict` is optional so external lists that only
+/// carry a verdict still parse.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerListEntry {
+    #[serde(rename = "steamID64", serialize_with = "serialize_steamid", deserialize_with = "deserialize_steamid")]
+    pub steamid: SteamID,
+    pub verdict: Verdict,
+    #[serde(default, rename = "customData")]
+    pub custom_data: serde_json::Value,
+    #[serde(default, rename = "previousNames")]
+    pub previous_names: Vec<Arc<str>>,
+}
+
+/// A batch of shared player-list entries, as imported from or exported to a remote
+/// community bot list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerListBatch {
+    pub version: u32,
+    #[serde(default)]
+    pub description: Option<Arc<str>>,
+    pub players: Vec<PlayerListEntry>,
+}
+
+impl PlayerListBatch {
+    pub fn new(players: Vec<PlayerListEntry>) -> Self {
+        Self {
+            version: PLAYER_LIST_SCHEMA_VERSION,
+            description: None,
+            players,
+        }
+    }
+}
+
+/// A verdict contributed by an external source (a community bot list, etc), kept
+/// alongside a player's own `local_verdict` rather than overwriting it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourcedVerdict {
+    pub source: Arc<str>,
+    pub verdict: Verdict,
+}
+
+/// How to resolve a conflict between a player's `local_verdict` and a verdict
+/// contributed by an imported remote list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MergePolicy {
+    LocalWins,
+    RemoteWins,
+    MostSevereWins,
+}
+
+impl MergePolicy {
+    /// Resolves a conflict between `local` and `remote`. Never mutates `local_verdict`
+    /// itself — the result is only used to derive `Player::effective_verdict`.
+    pub fn resolve(self, local: Verdict, remote: Verdict) -> Verdict {
+        match self {
+            MergePolicy::LocalWins => local,
+            MergePolicy::RemoteWins => remote,
+            MergePolicy::MostSevereWins => {
+                if remote.severity() > local.severity() {
+                    remote
+                } else {
+                    local
+                }
+            }
+        }
+    }
+}
+
+/// A single remote shared player-list source.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteListSource {
+    pub name: Arc<str>,
+    pub url: Arc<str>,
+}
+
+/// Settings for the remote list import subsystem.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteListSettings {
+    pub sources: Vec<RemoteListSource>,
+    #[serde(with = "duration_secs")]
+    pub refresh_interval: Duration,
+    pub merge_policy: MergePolicy,
+}
+
+impl Default for RemoteListSettings {
+    fn default() -> Self {
+        Self {
+            sources: Vec::new(),
+            refresh_interval: Duration::from_secs(3600),
+            merge_policy: MergePolicy::MostSevereWins,
+        }
+    }
+}
+
+mod duration_secs {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_u64(duration.as_secs())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Duration, D::Error> {
+        Ok(Duration::from_secs(u64::deserialize(d)?))
+    }
+}
+
+/// Error fetching or parsing a remote player-list batch.
+#[derive(Debug)]
+pub enum RemoteListError {
+    Request(reqwest::Error),
+    Parse(reqwest::Error),
+    UnsupportedVersion(u32),
+}
+
+impl std::fmt::Display for RemoteListError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RemoteListError::Request(e) => write!(f, "request failed: {e}"),
+            RemoteListError::Parse(e) => write!(f, "failed to parse batch: {e}"),
+            RemoteListError::UnsupportedVersion(v) => {
+                write!(f, "batch uses unsupported schema version {v}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RemoteListError {}
+
+/// Fetches and parses a single remote source's player-list batch.
+pub async fn fetch_remote_list(
+    client: &reqwest::Client,
+    source: &RemoteListSource,
+) -> Result<PlayerListBatch, RemoteListError> {
+    let response = client
+        .get(source.url.as_ref())
+        .send()
+        .await
+        .map_err(RemoteListError::Request)?;
+    let batch: PlayerListBatch = response.json().await.map_err(RemoteListError::Parse)?;
+
+    if batch.version > PLAYER_LIST_SCHEMA_VERSION {
+        return Err(RemoteListError::UnsupportedVersion(batch.version));
+    }
+
+    Ok(batch)
+}
+
+/// The most severe verdict among `sources`, or `Verdict::Player` if none have weighed in.
+fn remote_consensus(sources: &[SourcedVerdict]) -> Verdict {
+    sources
+        .iter()
+        .map(|s| s.verdict)
+        .max_by_key(|v| v.severity())
+        .unwrap_or(Verdict::Player)
+}
+
+/// Applies every entry in `batch` (attributed to `source`) onto the tracked players:
+/// updates each player's `sources` list and recomputes `remote_convicted` from the
+/// remote consensus alone, without ever touching `local_verdict` or the user-owned
+/// `convicted` flag. `policy` only feeds `effective_verdict`, a display-only merge of
+/// `local_verdict` and the remote consensus.
+pub fn apply_remote_batch(
+    players: &mut HashMap<SteamID, Player>,
+    source: &str,
+    batch: PlayerListBatch,
+    policy: MergePolicy,
+) {
+    for entry in batch.players {
+        let Some(player) = players.get_mut(&entry.steamid) else {
+            continue;
+        };
+
+        player.sources.retain(|s| s.source.as_ref() != source);
+        player.sources.push(SourcedVerdict {
+            source: Arc::from(source),
+            verdict: entry.verdict,
+        });
+
+        let remote_consensus = remote_consensus(&player.sources);
+        player.remote_convicted = matches!(remote_consensus, Verdict::Cheater | Verdict::Bot);
+        player.effective_verdict = policy.resolve(player.local_verdict, remote_consensus);
+    }
+}
+
+/// Refreshes every configured remote list in turn, applying each against `players`.
+pub async fn refresh_remote_lists(
+    client: &reqwest::Client,
+    players: &mut HashMap<SteamID, Player>,
+    settings: &RemoteListSettings,
+) {
+    for source in &settings.sources {
+        match fetch_remote_list(client, source).await {
+            Ok(batch) => apply_remote_batch(players, &source.name, batch, settings.merge_policy),
+            Err(e) => tracing::warn!("Failed to refresh remote list {}: {e}", source.name),
+        }
+    }
+}
+
+/// Builds an exportable batch from local player records, suitable for sharing as a
+/// community list.
+pub fn export_batch(records: impl IntoIterator<Item = PlayerRecord>) -> PlayerListBatch {
+    PlayerListBatch::new(
+        records
+            .into_iter()
+            .map(|record| PlayerListEntry {
+                steamid: record.steamid,
+                verdict: record.verdict,
+                custom_data: record.custom_data,
+                previous_names: record.previous_names,
+            })
+            .collect(),
+    )
+}
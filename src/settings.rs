@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+/// User-configurable settings, persisted to disk as JSON.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Settings {
+    #[serde(default)]
+    pub discord_rich_presence: DiscordRichPresenceSettings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            discord_rich_presence: DiscordRichPresenceSettings::default(),
+        }
+    }
+}
+
+/// Configuration for the optional Discord Rich Presence integration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscordRichPresenceSettings {
+    pub enabled: bool,
+    pub client_id: Arc<str>,
+}
+
+impl Default for DiscordRichPresenceSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            // Placeholder application id; users wire up their own via settings.
+            client_id: Arc::from("0"),
+        }
+    }
+}
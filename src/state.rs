@@ -0,0 +1,273 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use steamid_ng::SteamID;
+
+use crate::discord::DiscordPresence;
+use crate::player::{Friend, Player, SteamInfo};
+use crate::player_records::{PlayerRecord, Verdict};
+use crate::web::{Event, EventBroadcaster};
+
+/// Thresholds for the friend-network bot-cluster detector.
+#[derive(Debug, Clone)]
+pub struct FriendCorrelationConfig {
+    /// Minimum fraction of a player's resolvable friends that must be convicted
+    /// cheaters/bots before the player is auto-tagged as a suspected alt.
+    pub suspect_fraction: f32,
+    /// Minimum absolute count of convicted friends, so a player with only a
+    /// couple of resolvable friends isn't flagged off a single coincidental match.
+    pub suspect_min_count: usize,
+    /// How recently two convicted accounts must have friended each other (per
+    /// `friend_since`) to be joined into the same cluster component. Keeps a
+    /// years-old, incidental friendship from linking otherwise-unrelated cheaters.
+    pub recent_friend_window: Duration,
+}
+
+impl Default for FriendCorrelationConfig {
+    fn default() -> Self {
+        Self {
+            suspect_fraction: 0.3,
+            suspect_min_count: 2,
+            recent_friend_window: Duration::from_secs(30 * 24 * 3600),
+        }
+    }
+}
+
+/// In-memory undirected graph of steamid friendships, built from `GetFriendList` lookups.
+#[derive(Debug, Default)]
+pub struct FriendGraph {
+    edges: HashMap<SteamID, HashMap<SteamID, u64>>,
+}
+
+impl FriendGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `steamid`'s friend list, adding a (`friend_since`-weighted) edge for each entry.
+    pub fn set_friends(&mut self, steamid: SteamID, friends: &[Friend]) {
+        for friend in friends {
+            self.edges
+                .entry(steamid)
+                .or_default()
+                .insert(friend.steamid, friend.friend_since);
+            self.edges
+                .entry(friend.steamid)
+                .or_default()
+                .insert(steamid, friend.friend_since);
+        }
+    }
+
+    pub fn neighbours(&self, steamid: &SteamID) -> impl Iterator<Item = &SteamID> {
+        self.edges.get(steamid).into_iter().flat_map(|m| m.keys())
+    }
+
+    /// Connected components of two or more accounts in the subgraph induced by
+    /// `convicted`, keyed by a cluster id. A convicted account with no qualifying
+    /// convicted friend gets no cluster id.
+    ///
+    /// Only convicted accounts participate, and only edges friended within
+    /// `recent_window` of `now` (a unix timestamp, like `Friend::friend_since`) are
+    /// followed, so two bots that are mutually friended *and recently added* get
+    /// grouped together once both are convicted. Seeds are visited in a fixed
+    /// (steamid) order so cluster ids stay stable across passes over an unchanged graph.
+    fn convicted_clusters(
+        &self,
+        convicted: &HashSet<SteamID>,
+        now: u64,
+        recent_window: Duration,
+    ) -> HashMap<SteamID, usize> {
+        let mut clusters = HashMap::new();
+        let mut visited = HashSet::new();
+        let mut next_id = 0;
+
+        let mut seeds: Vec<SteamID> = convicted.iter().copied().collect();
+        seeds.sort_by_key(|&steamid| u64::from(steamid));
+
+        for start in seeds {
+            if visited.contains(&start) {
+                continue;
+            }
+
+            let mut stack = vec![start];
+            let mut component = Vec::new();
+            visited.insert(start);
+            while let Some(node) = stack.pop() {
+                component.push(node);
+                for (&neighbour, &friend_since) in self.edges.get(&node).into_iter().flatten() {
+                    let recent = now.saturating_sub(friend_since) <= recent_window.as_secs();
+                    if convicted.contains(&neighbour) && recent && visited.insert(neighbour) {
+                        stack.push(neighbour);
+                    }
+                }
+            }
+
+            if component.len() > 1 {
+                for node in component {
+                    clusters.insert(node, next_id);
+                }
+                next_id += 1;
+            }
+        }
+
+        clusters
+    }
+}
+
+/// Runs the friend-network bot-cluster detector over `players`: tags accounts whose
+/// friend networks are disproportionately convicted as `suspected-alt`, and assigns a
+/// shared cluster id to convicted accounts that are mutually friended and recently
+/// added (per `now`, a unix timestamp), without overwriting anyone's `local_verdict`.
+pub fn correlate_friend_networks(
+    players: &mut HashMap<SteamID, Player>,
+    graph: &FriendGraph,
+    config: &FriendCorrelationConfig,
+    now: u64,
+) {
+    let convicted: HashSet<SteamID> = players
+        .values()
+        .filter(|p| p.convicted || matches!(p.local_verdict, Verdict::Cheater | Verdict::Bot))
+        .map(|p| p.steamid)
+        .collect();
+
+    let clusters = graph.convicted_clusters(&convicted, now, config.recent_friend_window);
+    let suspected_alt_tag: Arc<str> = Arc::from("suspected-alt");
+
+    for player in players.values_mut() {
+        player.friend_cluster = clusters.get(&player.steamid).copied();
+
+        let resolvable: Vec<SteamID> = graph.neighbours(&player.steamid).copied().collect();
+        let is_suspect = !resolvable.is_empty() && {
+            let convicted_friends = resolvable.iter().filter(|id| convicted.contains(id)).count();
+            let fraction = convicted_friends as f32 / resolvable.len() as f32;
+            convicted_friends >= config.suspect_min_count && fraction >= config.suspect_fraction
+        };
+
+        // Reconcile the auto-tag every pass so it's dropped once the friend graph or
+        // conviction set no longer supports it, rather than accumulating forever.
+        let already_tagged = player.tags.contains(&suspected_alt_tag);
+        if is_suspect && !already_tagged {
+            player.tags.push(suspected_alt_tag.clone());
+        } else if !is_suspect && already_tagged {
+            player.tags.retain(|tag| tag != &suspected_alt_tag);
+        }
+    }
+}
+
+/// Advances every tracked player's `GameInfo` by one cycle, pruning anyone who's been
+/// gone too long and publishing the resulting `StateChanged`/`PlayerDisconnected`
+/// events to `broadcaster`. Clears `discord`'s activity if the pruned player is the
+/// local user, since that means the TF2 session itself has ended.
+pub fn advance_cycle(
+    players: &mut HashMap<SteamID, Player>,
+    broadcaster: &EventBroadcaster,
+    discord: &mut DiscordPresence,
+) {
+    players.retain(|&steamid, player| {
+        if player.game_info.should_prune() {
+            broadcaster.publish(Event::PlayerDisconnected { steamid });
+            if player.is_self {
+                discord.clear();
+            }
+            return false;
+        }
+
+        if player.game_info.next_cycle() {
+            broadcaster.publish(Event::StateChanged {
+                steamid,
+                state: player.game_info.state,
+            });
+        }
+
+        true
+    });
+}
+
+/// Acknowledges that `steamid` was seen this cycle, inserting a fresh `Player` and
+/// publishing `PlayerConnected` if it's new, or un-disconnecting an existing one and
+/// publishing `StateChanged` if its state flipped.
+pub fn acknowledge_player(
+    players: &mut HashMap<SteamID, Player>,
+    mut player: Player,
+    broadcaster: &EventBroadcaster,
+) {
+    let steamid = player.steamid;
+
+    match players.get_mut(&steamid) {
+        Some(existing) => {
+            if existing.game_info.acknowledge() {
+                broadcaster.publish(Event::StateChanged {
+                    steamid,
+                    state: existing.game_info.state,
+                });
+            }
+        }
+        None => {
+            player.game_info.acknowledge();
+            players.insert(steamid, player);
+            broadcaster.publish(Event::PlayerConnected { steamid });
+        }
+    }
+}
+
+/// Resolves `info` onto the tracked player, records its friend list into `graph` for
+/// the bot-cluster detector, and publishes `SteamInfoResolved`.
+pub fn resolve_steam_info(
+    players: &mut HashMap<SteamID, Player>,
+    steamid: SteamID,
+    info: SteamInfo,
+    graph: &mut FriendGraph,
+    broadcaster: &EventBroadcaster,
+) {
+    graph.set_friends(steamid, &info.friends);
+
+    if let Some(player) = players.get_mut(&steamid) {
+        player.set_steam_info(info.clone());
+        broadcaster.publish(Event::SteamInfoResolved {
+            steamid,
+            steam_info: info,
+        });
+    }
+}
+
+/// Applies a persisted record to the tracked player and publishes `VerdictChanged`
+/// if the record's verdict differs from what was already set.
+pub fn apply_player_record(
+    players: &mut HashMap<SteamID, Player>,
+    record: PlayerRecord,
+    broadcaster: &EventBroadcaster,
+) {
+    let Some(player) = players.get_mut(&record.steamid) else {
+        return;
+    };
+
+    let steamid = record.steamid;
+    let verdict = record.verdict;
+    if player.update_from_record(record) {
+        broadcaster.publish(Event::VerdictChanged { steamid, verdict });
+    }
+}
+
+/// Publishes `StatsUpdated` for `steamid`'s current kill/death/ping/loss counters.
+pub fn publish_stats_update(player: &Player, broadcaster: &EventBroadcaster) {
+    broadcaster.publish(Event::StatsUpdated {
+        steamid: player.steamid,
+        kills: player.game_info.kills,
+        deaths: player.game_info.deaths,
+        ping: player.game_info.ping,
+        loss: player.game_info.loss,
+    });
+}
+
+/// Refreshes `discord`'s activity from the current tracked players and session info.
+/// Call whenever a new `Verdict` is assigned or `GameInfo.state` transitions.
+pub fn sync_discord_presence(
+    players: &HashMap<SteamID, Player>,
+    server_name: &str,
+    map_name: &str,
+    settings: &crate::settings::DiscordRichPresenceSettings,
+    discord: &mut DiscordPresence,
+) {
+    discord.update(settings, server_name, map_name, players);
+}
@@ -0,0 +1,400 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rand::Rng;
+use reqwest::{Client, Response, StatusCode};
+use steamid_ng::SteamID;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+use crate::player::{Friend, ProfileVisibility, SteamInfo};
+
+const STEAM_API_BASE: &str = "https://api.steampowered.com";
+const PLAYER_SUMMARIES_ENDPOINT: &str = "ISteamUser/GetPlayerSummaries/v2";
+const PLAYER_BANS_ENDPOINT: &str = "ISteamUser/GetPlayerBans/v1";
+const FRIEND_LIST_ENDPOINT: &str = "ISteamUser/GetFriendList/v1";
+
+/// Configuration for [`SteamApiClient`]'s rate limiting and retry behaviour.
+#[derive(Debug, Clone)]
+pub struct SteamApiClientConfig {
+    /// Maximum number of retries after a 429 before giving up on a request.
+    pub max_retries: u32,
+    /// Base delay used for exponential backoff when Steam doesn't send a `Retry-After` header.
+    pub base_backoff: Duration,
+    /// Daily quota to divide into a steady per-second token refill rate, per endpoint.
+    pub requests_per_day: u32,
+}
+
+impl Default for SteamApiClientConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            base_backoff: Duration::from_millis(500),
+            // Steam Web API's documented soft limit.
+            requests_per_day: 100_000,
+        }
+    }
+}
+
+/// Error returned by a failed Steam Web API call.
+///
+/// Holds on to the final [`Response`] (if one was ever received) so callers can
+/// inspect its status and body, e.g. to tell a request Steam rejected with an error
+/// status from one that was still rate-limited (429) after every retry.
+#[derive(Debug)]
+pub struct SteamApiError {
+    message: String,
+    retries: u32,
+    response: Option<Response>,
+}
+
+impl SteamApiError {
+    fn new(message: impl Into<String>, retries: u32, response: Option<Response>) -> Self {
+        Self {
+            message: message.into(),
+            retries,
+            response,
+        }
+    }
+
+    /// How many retries were attempted before this error was returned.
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Borrow the response that caused this error, if one was received.
+    pub fn response(&self) -> Option<&Response> {
+        self.response.as_ref()
+    }
+
+    /// Take ownership of the response that caused this error, if one was received.
+    pub fn take_response(&mut self) -> Option<Response> {
+        self.response.take()
+    }
+}
+
+impl std::fmt::Display for SteamApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "steam api request failed after {} retries: {}", self.retries, self.message)
+    }
+}
+
+impl std::error::Error for SteamApiError {}
+
+/// A simple token-bucket limiter scoped to a single Steam Web API endpoint.
+///
+/// Each endpoint gets its own bucket so a burst against `GetPlayerSummaries` doesn't
+/// starve out unrelated calls to e.g. `GetFriendList`.
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(refill_per_sec: f64) -> Self {
+        // Allow a short burst on top of the steady refill rate.
+        let capacity = (refill_per_sec * 5.0).max(1.0);
+        Self {
+            capacity,
+            tokens: capacity,
+            refill_per_sec,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// Takes a token if one is available, otherwise returns how long to wait for one.
+    fn try_acquire(&mut self) -> Option<Duration> {
+        self.refill();
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            let deficit = 1.0 - self.tokens;
+            Some(Duration::from_secs_f64(deficit / self.refill_per_sec))
+        }
+    }
+}
+
+/// A rate-limited, retrying client for the Steam Web API.
+///
+/// Requests are throttled per-endpoint via an in-memory token bucket sized off
+/// [`SteamApiClientConfig::requests_per_day`], and a 429 response is retried with
+/// the server's `Retry-After` header (falling back to exponential backoff with
+/// jitter) up to `max_retries` times.
+pub struct SteamApiClient {
+    http: Client,
+    api_key: Arc<str>,
+    config: SteamApiClientConfig,
+    buckets: Mutex<HashMap<&'static str, TokenBucket>>,
+}
+
+impl SteamApiClient {
+    pub fn new(api_key: Arc<str>) -> Self {
+        Self::with_config(api_key, SteamApiClientConfig::default())
+    }
+
+    pub fn with_config(api_key: Arc<str>, config: SteamApiClientConfig) -> Self {
+        Self {
+            http: Client::new(),
+            api_key,
+            config,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn acquire(&self, endpoint: &'static str) {
+        loop {
+            let wait = {
+                let mut buckets = self.buckets.lock().await;
+                let refill_per_sec = self.config.requests_per_day as f64 / 86_400.0;
+                let bucket = buckets
+                    .entry(endpoint)
+                    .or_insert_with(|| TokenBucket::new(refill_per_sec));
+                bucket.try_acquire()
+            };
+
+            match wait {
+                None => return,
+                Some(duration) => tokio::time::sleep(duration).await,
+            }
+        }
+    }
+
+    fn backoff_for(&self, retries: u32) -> Duration {
+        let exp = self.config.base_backoff * 2u32.saturating_pow(retries);
+        let jitter = Duration::from_millis(rand::thread_rng().gen_range(0..250));
+        exp + jitter
+    }
+
+    /// Issues a GET request against `endpoint`, respecting its rate limit and retrying
+    /// on 429 with `Retry-After` (or exponential backoff) up to `max_retries` times.
+    async fn get_with_retry(&self, endpoint: &'static str, url: &str) -> Result<Response, SteamApiError> {
+        let mut retries = 0;
+        loop {
+            self.acquire(endpoint).await;
+
+            let response = self
+                .http
+                .get(url)
+                .send()
+                .await
+                .map_err(|e| SteamApiError::new(e.to_string(), retries, None))?;
+
+            if response.status() != StatusCode::TOO_MANY_REQUESTS {
+                if !response.status().is_success() {
+                    return Err(SteamApiError::new(
+                        format!("unexpected status {}", response.status()),
+                        retries,
+                        Some(response),
+                    ));
+                }
+                return Ok(response);
+            }
+
+            if retries >= self.config.max_retries {
+                return Err(SteamApiError::new("rate limited", retries, Some(response)));
+            }
+
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok())
+                .map(Duration::from_secs);
+
+            let backoff = retry_after.unwrap_or_else(|| self.backoff_for(retries));
+            tracing::warn!("Steam API rate limited on {endpoint}, retrying in {backoff:?}");
+            tokio::time::sleep(backoff).await;
+            retries += 1;
+        }
+    }
+
+    /// Fetches `SteamInfo` (profile + ban data + friend list) for a batch of up to 100
+    /// steamids, keyed by steamid since Steam doesn't guarantee the response order
+    /// matches the request.
+    pub async fn get_player_summaries(
+        &self,
+        steamids: &[SteamID],
+    ) -> Result<HashMap<SteamID, SteamInfo>, SteamApiError> {
+        let ids = steamids
+            .iter()
+            .map(|s| u64::from(*s).to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let summaries_url = format!(
+            "{STEAM_API_BASE}/{PLAYER_SUMMARIES_ENDPOINT}/?key={}&steamids={ids}",
+            self.api_key
+        );
+        let bans_url = format!(
+            "{STEAM_API_BASE}/{PLAYER_BANS_ENDPOINT}/?key={}&steamids={ids}",
+            self.api_key
+        );
+
+        let summaries_resp = self
+            .get_with_retry(PLAYER_SUMMARIES_ENDPOINT, &summaries_url)
+            .await?;
+        let summaries: PlayerSummariesResponse = summaries_resp
+            .json()
+            .await
+            .map_err(|e| SteamApiError::new(e.to_string(), 0, None))?;
+
+        let bans_resp = self.get_with_retry(PLAYER_BANS_ENDPOINT, &bans_url).await?;
+        let bans: PlayerBansResponse = bans_resp
+            .json()
+            .await
+            .map_err(|e| SteamApiError::new(e.to_string(), 0, None))?;
+
+        let bans_by_id: HashMap<String, PlayerBansEntry> =
+            bans.players.into_iter().map(|b| (b.steamid.clone(), b)).collect();
+
+        let mut infos = HashMap::with_capacity(summaries.response.players.len());
+        for summary in summaries.response.players {
+            let Some(steamid) = summary.steamid.parse::<u64>().ok().map(SteamID::from) else {
+                tracing::warn!("Steam API returned an unparseable steamid: {}", summary.steamid);
+                continue;
+            };
+
+            let bans = bans_by_id.get(&summary.steamid);
+            let profile_visibility = ProfileVisibility::from(summary.communityvisibilitystate);
+            let friends = match self.get_friend_list(steamid, profile_visibility.clone()).await {
+                Ok(friends) => friends.unwrap_or_default(),
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to fetch friend list for {}: {e}",
+                        u64::from(steamid)
+                    );
+                    Vec::new()
+                }
+            };
+
+            infos.insert(
+                steamid,
+                SteamInfo {
+                    account_name: summary.personaname.into(),
+                    profile_url: summary.profileurl.into(),
+                    pfp_url: summary.avatarfull.into(),
+                    pfp_hash: summary.avatarhash.into(),
+                    profile_visibility,
+                    time_created: summary.timecreated,
+                    country_code: summary.loccountrycode.map(Arc::from),
+                    vac_bans: bans.map(|b| b.number_of_vac_bans).unwrap_or(0),
+                    game_bans: bans.map(|b| b.number_of_game_bans).unwrap_or(0),
+                    days_since_last_ban: bans
+                        .filter(|b| b.number_of_vac_bans > 0 || b.number_of_game_bans > 0)
+                        .map(|b| b.days_since_last_ban),
+                    friends,
+                },
+            );
+        }
+
+        Ok(infos)
+    }
+
+    /// Fetches `steamid`'s friend list.
+    ///
+    /// Steam only exposes `GetFriendList` for profiles with public friend lists, so a
+    /// `Private`/`FriendsOnly` profile is skipped and reported as unresolved (`None`)
+    /// rather than as an empty friend list.
+    pub async fn get_friend_list(
+        &self,
+        steamid: SteamID,
+        visibility: ProfileVisibility,
+    ) -> Result<Option<Vec<Friend>>, SteamApiError> {
+        if visibility != ProfileVisibility::Public {
+            return Ok(None);
+        }
+
+        let url = format!(
+            "{STEAM_API_BASE}/{FRIEND_LIST_ENDPOINT}/?key={}&steamid={}&relationship=friend",
+            self.api_key,
+            u64::from(steamid)
+        );
+
+        let response = self.get_with_retry(FRIEND_LIST_ENDPOINT, &url).await?;
+        let parsed: FriendListResponse = response
+            .json()
+            .await
+            .map_err(|e| SteamApiError::new(e.to_string(), 0, None))?;
+
+        Ok(Some(
+            parsed
+                .friendslist
+                .friends
+                .into_iter()
+                .filter_map(|f| {
+                    Some(Friend {
+                        steamid: f.steamid.parse::<u64>().ok()?.into(),
+                        friend_since: f.friend_since,
+                    })
+                })
+                .collect(),
+        ))
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct FriendListResponse {
+    friendslist: FriendListInner,
+}
+
+#[derive(serde::Deserialize)]
+struct FriendListInner {
+    friends: Vec<FriendEntry>,
+}
+
+#[derive(serde::Deserialize)]
+struct FriendEntry {
+    steamid: String,
+    #[serde(rename = "friend_since")]
+    friend_since: u64,
+}
+
+#[derive(serde::Deserialize)]
+struct PlayerSummariesResponse {
+    response: PlayerSummariesInner,
+}
+
+#[derive(serde::Deserialize)]
+struct PlayerSummariesInner {
+    players: Vec<PlayerSummaryEntry>,
+}
+
+#[derive(serde::Deserialize)]
+struct PlayerSummaryEntry {
+    steamid: String,
+    personaname: String,
+    profileurl: String,
+    avatarfull: String,
+    avatarhash: String,
+    communityvisibilitystate: i32,
+    timecreated: Option<i64>,
+    loccountrycode: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct PlayerBansResponse {
+    players: Vec<PlayerBansEntry>,
+}
+
+#[derive(serde::Deserialize)]
+struct PlayerBansEntry {
+    #[serde(rename = "SteamId")]
+    steamid: String,
+    #[serde(rename = "NumberOfVACBans")]
+    number_of_vac_bans: i64,
+    #[serde(rename = "NumberOfGameBans")]
+    number_of_game_bans: i64,
+    #[serde(rename = "DaysSinceLastBan")]
+    days_since_last_ban: i64,
+}
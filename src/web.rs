@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use serde::Serialize;
+use steamid_ng::SteamID;
+use tokio::sync::broadcast;
+
+use crate::player::{PlayerState, SteamInfo};
+use crate::player_records::Verdict;
+
+/// Capacity of the broadcast channel backing [`EventBroadcaster`]. Slow subscribers that
+/// fall this far behind the latest event skip the events they missed (logging a warning)
+/// rather than having them buffered forever.
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// A diff-sized update pushed to subscribers of the `/ws` event stream, in place of
+/// re-serializing the whole tracked player list on every change.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Event {
+    PlayerConnected {
+        #[serde(rename = "steamID64", serialize_with = "crate::player::serialize_steamid_as_string")]
+        steamid: SteamID,
+    },
+    PlayerDisconnected {
+        #[serde(rename = "steamID64", serialize_with = "crate::player::serialize_steamid_as_string")]
+        steamid: SteamID,
+    },
+    StateChanged {
+        #[serde(rename = "steamID64", serialize_with = "crate::player::serialize_steamid_as_string")]
+        steamid: SteamID,
+        state: PlayerState,
+    },
+    SteamInfoResolved {
+        #[serde(rename = "steamID64", serialize_with = "crate::player::serialize_steamid_as_string")]
+        steamid: SteamID,
+        #[serde(rename = "steamInfo")]
+        steam_info: SteamInfo,
+    },
+    VerdictChanged {
+        #[serde(rename = "steamID64", serialize_with = "crate::player::serialize_steamid_as_string")]
+        steamid: SteamID,
+        verdict: Verdict,
+    },
+    StatsUpdated {
+        #[serde(rename = "steamID64", serialize_with = "crate::player::serialize_steamid_as_string")]
+        steamid: SteamID,
+        kills: u32,
+        deaths: u32,
+        ping: u32,
+        loss: u32,
+    },
+}
+
+/// Broadcasts [`Event`]s to any number of concurrent `/ws` subscribers.
+///
+/// Cheap to clone: internally just a `broadcast::Sender`, so every subscriber gets its
+/// own receiver over the same underlying channel.
+#[derive(Debug, Clone)]
+pub struct EventBroadcaster {
+    sender: broadcast::Sender<Event>,
+}
+
+impl EventBroadcaster {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self { sender }
+    }
+
+    /// Publishes an event to all current subscribers. A no-op if nobody is listening.
+    pub fn publish(&self, event: Event) {
+        // An error here just means there are currently no subscribers.
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Event> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventBroadcaster {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Routes handling the push-based event stream. Nest this under the existing `web` router.
+pub fn ws_routes(broadcaster: EventBroadcaster) -> Router {
+    Router::new()
+        .route("/ws", get(ws_handler))
+        .with_state(Arc::new(broadcaster))
+}
+
+async fn ws_handler(
+    ws: WebSocketUpgrade,
+    State(broadcaster): State<Arc<EventBroadcaster>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_subscriber(socket, broadcaster))
+}
+
+async fn handle_subscriber(mut socket: WebSocket, broadcaster: Arc<EventBroadcaster>) {
+    let mut events = broadcaster.subscribe();
+
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                let event = match event {
+                    Ok(event) => event,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!("Event subscriber lagged, skipped {skipped} events");
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+
+                let payload = match serde_json::to_string(&event) {
+                    Ok(payload) => payload,
+                    Err(e) => {
+                        tracing::error!("Failed to serialize event: {e}");
+                        continue;
+                    }
+                };
+
+                if socket.send(Message::Text(payload)).await.is_err() {
+                    break;
+                }
+            }
+            message = socket.recv() => {
+                match message {
+                    Some(Ok(_)) => continue,
+                    _ => break,
+                }
+            }
+        }
+    }
+}